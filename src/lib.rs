@@ -5,22 +5,30 @@ use nalgebra::{
     RealField,
     linalg::SymmetricEigen,
 };
+use std::ops::{Add, AddAssign};
 
 /// A quaternion averager.
 /// Implemented as discussed [here](https://stackoverflow.com/questions/12374087/average-of-multiple-quaternions)
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct QuaternionAverager<T: RealField + Copy + PartialEq> {
     matrix: Matrix4<T>,
     weight_sum: T,
 }
 
+impl<T: RealField + Copy + PartialEq> Default for QuaternionAverager<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T: RealField + Copy + PartialEq> QuaternionAverager<T> {
     /// Creates and returns a new quaternion averager
-    /// 
+    ///
     /// # Example
-    /// 
+    ///
     /// ```
     /// use quaternion_averager::QuaternionAverager;
-    /// 
+    ///
     /// let mut qa = QuaternionAverager::<f64>::new();
     /// ```
     pub fn new() -> QuaternionAverager<T> {
@@ -30,6 +38,57 @@ impl<T: RealField + Copy + PartialEq> QuaternionAverager<T> {
         }
     }
 
+    /// Reconstructs an averager from a raw accumulator matrix and weight
+    /// sum, as returned by [`into_raw_parts`](Self::into_raw_parts).
+    ///
+    /// # Example
+    /// ```
+    /// use quaternion_averager::QuaternionAverager;
+    /// use nalgebra::Matrix4;
+    ///
+    /// let qa = QuaternionAverager::<f64>::from_raw_parts(Matrix4::zeros(), 0f64);
+    /// ```
+    pub fn from_raw_parts(matrix: Matrix4<T>, weight_sum: T) -> QuaternionAverager<T> {
+        QuaternionAverager { matrix, weight_sum }
+    }
+
+    /// Breaks the averager down into its raw accumulator matrix and weight
+    /// sum, for later rebuilding with [`from_raw_parts`](Self::from_raw_parts).
+    ///
+    /// # Example
+    /// ```
+    /// use quaternion_averager::QuaternionAverager;
+    ///
+    /// let qa = QuaternionAverager::<f64>::new();
+    /// let (matrix, weight_sum) = qa.into_raw_parts();
+    /// ```
+    pub fn into_raw_parts(self) -> (Matrix4<T>, T) {
+        (self.matrix, self.weight_sum)
+    }
+
+    /// Folds another averager's accumulator into this one
+    ///
+    /// # Example
+    /// ```
+    /// use quaternion_averager::QuaternionAverager;
+    /// use nalgebra::{
+    ///     geometry::Quaternion,
+    ///     geometry::UnitQuaternion,
+    /// };
+    ///
+    /// let q1 = Quaternion::new(0.9961947f64, 0.0871557f64, 0f64, 0f64);
+    /// let q1 = UnitQuaternion::from_quaternion(q1);
+    /// let mut a = QuaternionAverager::new();
+    /// a.add_quaternion(&q1);
+    /// let mut b = QuaternionAverager::new();
+    /// b.add_quaternion(&q1);
+    /// a.merge(&b);
+    /// ```
+    pub fn merge(&mut self, other: &QuaternionAverager<T>) {
+        self.matrix += other.matrix;
+        self.weight_sum += other.weight_sum;
+    }
+
     /// Add a new unit quaternion with weight 1 to the averager
     /// 
     /// # Example
@@ -52,8 +111,10 @@ impl<T: RealField + Copy + PartialEq> QuaternionAverager<T> {
         self.weight_sum += w;
     }
 
-    /// Add a new unit quaternion with custom weight to the averager
-    /// 
+    /// Add a new unit quaternion with custom weight to the averager.
+    /// Panics if `weight` is not positive, since the outer product is
+    /// divided by it.
+    ///
     /// # Example
     /// ```
     /// use quaternion_averager::QuaternionAverager;
@@ -61,21 +122,81 @@ impl<T: RealField + Copy + PartialEq> QuaternionAverager<T> {
     ///     geometry::Quaternion,
     ///     geometry::UnitQuaternion,
     /// };
-    /// 
+    ///
     /// let mut qa = QuaternionAverager::new();
     /// let q1 = Quaternion::new(0.9961947f32, 0.0871557f32, 0f32, 0f32);
     /// let q1 = UnitQuaternion::from_quaternion(q1);
     /// qa.add_quaternion_weighted(&q1, 0.25f32);
     /// ```
     pub fn add_quaternion_weighted(&mut self, quaternion: &UnitQuaternion<T>, weight: T) {
+        let zero = T::from_f32(0f32).unwrap();
+        assert!(weight > zero, "add_quaternion_weighted: weight must be positive");
+
         let q = quaternion.coords * quaternion.coords.transpose();
         let q = q / weight;
         self.matrix += q;
         self.weight_sum += weight;
     }
 
+    /// Removes a previously-added unit quaternion with weight 1 from the
+    /// averager, exactly undoing a matching `add_quaternion` call.
+    /// Removing a quaternion that was never added leaves the accumulator
+    /// invalid (no longer positive semi-definite), and `calc_average` then
+    /// returns a meaningless result; it panics only once the accumulated
+    /// weight has dropped to zero or below.
+    ///
+    /// # Example
+    /// ```
+    /// use quaternion_averager::QuaternionAverager;
+    /// use nalgebra::{
+    ///     geometry::Quaternion,
+    ///     geometry::UnitQuaternion,
+    /// };
+    ///
+    /// let mut qa = QuaternionAverager::new();
+    /// let q1 = Quaternion::new(0.9961947f32, 0.0871557f32, 0f32, 0f32);
+    /// let q1 = UnitQuaternion::from_quaternion(q1);
+    /// qa.add_quaternion(&q1);
+    /// qa.remove_quaternion(&q1);
+    /// ```
+    pub fn remove_quaternion(&mut self, quaternion: &UnitQuaternion<T>) {
+        let q = quaternion.coords * quaternion.coords.transpose();
+        self.matrix -= q;
+        let w = T::from_f32(1f32).unwrap();
+        self.weight_sum -= w;
+    }
+
+    /// Removes a previously-added unit quaternion with a custom weight from
+    /// the averager, exactly undoing a matching `add_quaternion_weighted`
+    /// call. See [`remove_quaternion`](Self::remove_quaternion). Panics if
+    /// `weight` is not positive, since the outer product is divided by it.
+    ///
+    /// # Example
+    /// ```
+    /// use quaternion_averager::QuaternionAverager;
+    /// use nalgebra::{
+    ///     geometry::Quaternion,
+    ///     geometry::UnitQuaternion,
+    /// };
+    ///
+    /// let mut qa = QuaternionAverager::new();
+    /// let q1 = Quaternion::new(0.9961947f32, 0.0871557f32, 0f32, 0f32);
+    /// let q1 = UnitQuaternion::from_quaternion(q1);
+    /// qa.add_quaternion_weighted(&q1, 0.25f32);
+    /// qa.remove_quaternion_weighted(&q1, 0.25f32);
+    /// ```
+    pub fn remove_quaternion_weighted(&mut self, quaternion: &UnitQuaternion<T>, weight: T) {
+        let zero = T::from_f32(0f32).unwrap();
+        assert!(weight > zero, "remove_quaternion_weighted: weight must be positive");
+
+        let q = quaternion.coords * quaternion.coords.transpose();
+        let q = q / weight;
+        self.matrix -= q;
+        self.weight_sum -= weight;
+    }
+
     /// Calculates and returns the quaternion average
-    /// 
+    ///
     /// # Example
     /// ```
     /// use quaternion_averager::QuaternionAverager;
@@ -95,17 +216,280 @@ impl<T: RealField + Copy + PartialEq> QuaternionAverager<T> {
     /// println!("The average of {} and {} is {}", q1, q2, qavg);
     /// ```
     pub fn calc_average(&self) -> UnitQuaternion<T> {
+        self.eigen_average().0
+    }
+
+    /// Calculates the quaternion average together with its dispersion, a
+    /// spread metric derived from the same eigenvalue spectrum that
+    /// `calc_average` already computes: `0.0` means every input quaternion
+    /// was identical, and the largest possible value, `0.75`, means the
+    /// inputs are spread evenly with no preferred orientation at all (the
+    /// largest of the four normalized eigenvalues is never below `0.25`).
+    /// Useful for sensor fusion pipelines that need to judge how
+    /// trustworthy an averaged attitude is.
+    ///
+    /// # Example
+    /// ```
+    /// use quaternion_averager::QuaternionAverager;
+    /// use nalgebra::{
+    ///     geometry::Quaternion,
+    ///     geometry::UnitQuaternion,
+    /// };
+    ///
+    /// let mut qa = QuaternionAverager::new();
+    /// let q1 = Quaternion::new(0.9961947f64, 0.0871557f64, 0f64, 0f64);
+    /// let q1 = UnitQuaternion::from_quaternion(q1);
+    /// qa.add_quaternion(&q1);
+    /// qa.add_quaternion(&q1);
+    /// let (qavg, dispersion) = qa.calc_average_with_dispersion();
+    /// assert!(dispersion < 0.00001f64);
+    /// ```
+    pub fn calc_average_with_dispersion(&self) -> (UnitQuaternion<T>, T) {
+        let (q, lambda0) = self.eigen_average();
+        let one = T::from_f32(1f32).unwrap();
+
+        (q, one - lambda0)
+    }
+
+    /// Returns just the dispersion half of [`calc_average_with_dispersion`](Self::calc_average_with_dispersion).
+    ///
+    /// # Example
+    /// ```
+    /// use quaternion_averager::QuaternionAverager;
+    /// use nalgebra::{
+    ///     geometry::Quaternion,
+    ///     geometry::UnitQuaternion,
+    /// };
+    ///
+    /// let mut qa = QuaternionAverager::new();
+    /// let q1 = Quaternion::new(0.9961947f64, 0.0871557f64, 0f64, 0f64);
+    /// let q1 = UnitQuaternion::from_quaternion(q1);
+    /// qa.add_quaternion(&q1);
+    /// qa.add_quaternion(&q1);
+    /// assert!(qa.dispersion() < 0.00001f64);
+    /// ```
+    pub fn dispersion(&self) -> T {
+        let one = T::from_f32(1f32).unwrap();
+        let (_, lambda0) = self.eigen_average();
+
+        one - lambda0
+    }
+
+    /// Approximates the RMS angular deviation (in radians) of the averaged
+    /// samples from their mean, via `2*acos(sqrt(lambda0))`. This is a rougher,
+    /// more interpretable companion to [`dispersion`](Self::dispersion).
+    ///
+    /// # Example
+    /// ```
+    /// use quaternion_averager::QuaternionAverager;
+    /// use nalgebra::{
+    ///     geometry::Quaternion,
+    ///     geometry::UnitQuaternion,
+    /// };
+    ///
+    /// let mut qa = QuaternionAverager::new();
+    /// let q1 = Quaternion::new(0.9961947f64, 0.0871557f64, 0f64, 0f64);
+    /// let q1 = UnitQuaternion::from_quaternion(q1);
+    /// qa.add_quaternion(&q1);
+    /// qa.add_quaternion(&q1);
+    /// assert!(qa.angular_deviation() < 0.00001f64);
+    /// ```
+    pub fn angular_deviation(&self) -> T {
+        let (_, lambda0) = self.eigen_average();
+        let two = T::from_f32(2f32).unwrap();
+
+        two * lambda0.sqrt().acos()
+    }
+
+    /// Runs the weight-normalized eigendecomposition shared by `calc_average`
+    /// and the dispersion accessors, returning the dominant eigenvector as
+    /// the mean quaternion alongside `lambda0`, its (largest) eigenvalue as a
+    /// fraction of the trace (the sum of all four eigenvalues), clamped to
+    /// `[0, 1]` to absorb floating-point round-off.
+    ///
+    /// Panics if `weight_sum` is zero or negative, which happens if no
+    /// quaternions were added, or if `remove_quaternion`/
+    /// `remove_quaternion_weighted` removed as much weight as was ever added,
+    /// or more.
+    fn eigen_average(&self) -> (UnitQuaternion<T>, T) {
+        let zero = T::from_f32(0f32).unwrap();
+        let one = T::from_f32(1f32).unwrap();
+        assert!(
+            self.weight_sum > zero,
+            "eigen_average: weight_sum must be positive, got an averager with zero or negative accumulated weight"
+        );
+
         let m = self.matrix / self.weight_sum;
         let decomp = SymmetricEigen::new(m);
         let i = decomp.eigenvalues.imax();
+        let lambda0 = (decomp.eigenvalues[i] / m.trace()).clamp(zero, one);
         let q = decomp.eigenvectors.column(i);
         let q = Quaternion::new(q[3], q[0], q[1], q[2]);
         let q = UnitQuaternion::from_quaternion(q);
 
-        q
+        (q, lambda0)
+    }
+
+    /// Averages a one-off collection of unit quaternions with weight 1 each
+    /// and returns the result directly, without having to build and hold
+    /// onto a `QuaternionAverager` yourself.
+    ///
+    /// # Example
+    /// ```
+    /// use quaternion_averager::QuaternionAverager;
+    /// use nalgebra::{
+    ///     geometry::Quaternion,
+    ///     geometry::UnitQuaternion,
+    /// };
+    ///
+    /// let q1 = UnitQuaternion::from_quaternion(Quaternion::new(0.9961947f64, 0.0871557f64, 0f64, 0f64));
+    /// let q2 = UnitQuaternion::from_quaternion(Quaternion::new(0.9848078f64, 0.1736482f64, 0f64, 0f64));
+    /// let qavg = QuaternionAverager::mean_of([q1, q2]);
+    /// ```
+    pub fn mean_of(iter: impl IntoIterator<Item = UnitQuaternion<T>>) -> UnitQuaternion<T> {
+        let mut qa = QuaternionAverager::new();
+        qa.extend(iter);
+        assert!(qa.matrix != Matrix4::zeros(), "mean_of: no quaternions to average");
+        qa.calc_average()
+    }
+
+    /// Averages a one-off collection of `(quaternion, weight)` pairs and
+    /// returns the result directly, without having to build and hold onto a
+    /// `QuaternionAverager` yourself.
+    ///
+    /// # Example
+    /// ```
+    /// use quaternion_averager::QuaternionAverager;
+    /// use nalgebra::{
+    ///     geometry::Quaternion,
+    ///     geometry::UnitQuaternion,
+    /// };
+    ///
+    /// let q1 = UnitQuaternion::from_quaternion(Quaternion::new(0.9961947f64, 0.0871557f64, 0f64, 0f64));
+    /// let q2 = UnitQuaternion::from_quaternion(Quaternion::new(0.9848078f64, 0.1736482f64, 0f64, 0f64));
+    /// let qavg = QuaternionAverager::weighted_mean_of([(q1, 0.75f64), (q2, 0.25f64)]);
+    /// ```
+    pub fn weighted_mean_of(iter: impl IntoIterator<Item = (UnitQuaternion<T>, T)>) -> UnitQuaternion<T> {
+        let mut qa = QuaternionAverager::new();
+        for (q, weight) in iter {
+            qa.add_quaternion_weighted(&q, weight);
+        }
+        assert!(qa.matrix != Matrix4::zeros(), "weighted_mean_of: no quaternions to average");
+        qa.calc_average()
+    }
+}
+
+impl<T: RealField + Copy + PartialEq> FromIterator<UnitQuaternion<T>> for QuaternionAverager<T> {
+    fn from_iter<I: IntoIterator<Item = UnitQuaternion<T>>>(iter: I) -> Self {
+        let mut qa = QuaternionAverager::new();
+        qa.extend(iter);
+        qa
+    }
+}
+
+impl<T: RealField + Copy + PartialEq> Extend<UnitQuaternion<T>> for QuaternionAverager<T> {
+    fn extend<I: IntoIterator<Item = UnitQuaternion<T>>>(&mut self, iter: I) {
+        for q in iter {
+            self.add_quaternion(&q);
+        }
+    }
+}
+
+impl<T: RealField + Copy + PartialEq> AddAssign<&QuaternionAverager<T>> for QuaternionAverager<T> {
+    fn add_assign(&mut self, other: &QuaternionAverager<T>) {
+        self.merge(other);
+    }
+}
+
+impl<T: RealField + Copy + PartialEq> Add for QuaternionAverager<T> {
+    type Output = QuaternionAverager<T>;
+
+    fn add(mut self, other: QuaternionAverager<T>) -> QuaternionAverager<T> {
+        self.merge(&other);
+        self
+    }
+}
+
+/// Takes the shorter arc between `a` and `b`: if their coordinate vectors
+/// point into opposite hemispheres, negates `b` (and the cached dot product)
+/// so blending two near-antipodal quaternions doesn't spin the long way
+/// around. This is the same sign convention the eigenvalue averager
+/// implicitly relies on, since `q` and `-q` represent the same rotation.
+fn shorter_arc<T: RealField + Copy>(b: &UnitQuaternion<T>, d: T) -> (UnitQuaternion<T>, T) {
+    let zero = T::from_f32(0f32).unwrap();
+    if d < zero {
+        let b = UnitQuaternion::new_unchecked(Quaternion::from_vector(-b.coords));
+        (b, -d)
+    } else {
+        (*b, d)
     }
 }
 
+/// Spherically interpolates between two unit quaternions, taking the shorter
+/// arc. Falls back to [`nlerp_blend`] when `a` and `b` are nearly coincident,
+/// where the slerp formula would divide by a near-zero `sin(theta)`.
+///
+/// Much cheaper than building a `QuaternionAverager` and running the full
+/// 4x4 eigensolve for the common case of blending exactly two orientations.
+///
+/// # Example
+/// ```
+/// use quaternion_averager::slerp_blend;
+/// use nalgebra::{
+///     geometry::Quaternion,
+///     geometry::UnitQuaternion,
+/// };
+///
+/// let q1 = UnitQuaternion::from_quaternion(Quaternion::new(0.9961947f64, 0.0871557f64, 0f64, 0f64));
+/// let q2 = UnitQuaternion::from_quaternion(Quaternion::new(0.9848078f64, 0.1736482f64, 0f64, 0f64));
+/// let qmid = slerp_blend(&q1, &q2, 0.5f64);
+/// ```
+pub fn slerp_blend<T: RealField + Copy>(a: &UnitQuaternion<T>, b: &UnitQuaternion<T>, t: T) -> UnitQuaternion<T> {
+    let d = a.coords.dot(&b.coords);
+    let (b, d) = shorter_arc(b, d);
+
+    let near_one = T::from_f32(0.9995f32).unwrap();
+    if d > near_one {
+        return nlerp_blend(a, &b, t);
+    }
+
+    let one = T::from_f32(1f32).unwrap();
+    let theta = d.acos();
+    let sin_theta = theta.sin();
+    let wa = ((one - t) * theta).sin() / sin_theta;
+    let wb = (t * theta).sin() / sin_theta;
+    let coords = a.coords * wa + b.coords * wb;
+
+    UnitQuaternion::from_quaternion(Quaternion::from_vector(coords))
+}
+
+/// Normalized-linear-interpolates between two unit quaternions, taking the
+/// shorter arc. Cheaper and less accurate than [`slerp_blend`], but well
+/// behaved everywhere, including where slerp needs its near-coincident
+/// fallback.
+///
+/// # Example
+/// ```
+/// use quaternion_averager::nlerp_blend;
+/// use nalgebra::{
+///     geometry::Quaternion,
+///     geometry::UnitQuaternion,
+/// };
+///
+/// let q1 = UnitQuaternion::from_quaternion(Quaternion::new(0.9961947f64, 0.0871557f64, 0f64, 0f64));
+/// let q2 = UnitQuaternion::from_quaternion(Quaternion::new(0.9848078f64, 0.1736482f64, 0f64, 0f64));
+/// let qmid = nlerp_blend(&q1, &q2, 0.5f64);
+/// ```
+pub fn nlerp_blend<T: RealField + Copy>(a: &UnitQuaternion<T>, b: &UnitQuaternion<T>, t: T) -> UnitQuaternion<T> {
+    let d = a.coords.dot(&b.coords);
+    let (b, _) = shorter_arc(b, d);
+
+    let one = T::from_f32(1f32).unwrap();
+    let coords = a.coords * (one - t) + b.coords * t;
+
+    UnitQuaternion::from_quaternion(Quaternion::from_vector(coords))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -144,4 +528,261 @@ mod tests {
 
         assert_relative_eq!(q, qr, max_relative = 0.000001);
     }
+
+    #[test]
+    fn mean_of_matches_manual_average() {
+        let q1 = Quaternion::new(0.9961947f64, 0.0871557f64, 0f64, 0f64);
+        let q1 = UnitQuaternion::from_quaternion(q1);
+        let q2 = Quaternion::new(0.9848078f64, 0.1736482f64, 0f64, 0f64);
+        let q2 = UnitQuaternion::from_quaternion(q2);
+
+        let q = QuaternionAverager::mean_of([q1, q2]);
+
+        let mut avg = QuaternionAverager::new();
+        avg.add_quaternion(&q1);
+        avg.add_quaternion(&q2);
+        let qr = avg.calc_average();
+
+        assert_relative_eq!(q, qr, max_relative = 0.000001);
+    }
+
+    #[test]
+    fn collect_from_iterator_matches_manual_average() {
+        let q1 = Quaternion::new(0.9961947f64, 0.0871557f64, 0f64, 0f64);
+        let q1 = UnitQuaternion::from_quaternion(q1);
+        let q2 = Quaternion::new(0.9848078f64, 0.1736482f64, 0f64, 0f64);
+        let q2 = UnitQuaternion::from_quaternion(q2);
+
+        let qa: QuaternionAverager<f64> = [q1, q2].into_iter().collect();
+
+        let mut avg = QuaternionAverager::new();
+        avg.add_quaternion(&q1);
+        avg.add_quaternion(&q2);
+
+        assert_relative_eq!(qa.calc_average(), avg.calc_average(), max_relative = 0.000001);
+    }
+
+    #[test]
+    fn dispersion_is_zero_for_identical_quaternions() {
+        let mut avg = QuaternionAverager::new();
+        let q1 = Quaternion::new(0.9961947f64, 0.0871557f64, 0f64, 0f64);
+        let q1 = UnitQuaternion::from_quaternion(q1);
+        avg.add_quaternion(&q1);
+        avg.add_quaternion(&q1);
+
+        let (qavg, dispersion) = avg.calc_average_with_dispersion();
+
+        assert_relative_eq!(qavg, q1, max_relative = 0.00001);
+        assert!(dispersion.abs() < 0.00001f64);
+        assert_relative_eq!(avg.dispersion(), dispersion, max_relative = 0.00001);
+    }
+
+    #[test]
+    fn dispersion_grows_with_disagreement() {
+        let mut avg = QuaternionAverager::new();
+        let q1 = Quaternion::new(0.9961947f64, 0.0871557f64, 0f64, 0f64);
+        let q1 = UnitQuaternion::from_quaternion(q1);
+        let q2 = Quaternion::new(0f64, 1f64, 0f64, 0f64);
+        let q2 = UnitQuaternion::from_quaternion(q2);
+        avg.add_quaternion(&q1);
+        avg.add_quaternion(&q2);
+
+        assert!(avg.dispersion() > 0.1f64);
+    }
+
+    #[test]
+    fn remove_quaternion_undoes_add_quaternion() {
+        let q1 = Quaternion::new(0.9961947f64, 0.0871557f64, 0f64, 0f64);
+        let q1 = UnitQuaternion::from_quaternion(q1);
+        let q2 = Quaternion::new(0.9848078f64, 0.1736482f64, 0f64, 0f64);
+        let q2 = UnitQuaternion::from_quaternion(q2);
+
+        let mut avg = QuaternionAverager::new();
+        avg.add_quaternion(&q1);
+        avg.add_quaternion(&q2);
+        avg.add_quaternion(&q2);
+        avg.remove_quaternion(&q2);
+
+        let mut expected = QuaternionAverager::new();
+        expected.add_quaternion(&q1);
+        expected.add_quaternion(&q2);
+
+        assert_relative_eq!(avg.calc_average(), expected.calc_average(), max_relative = 0.000001);
+    }
+
+    #[test]
+    fn remove_quaternion_weighted_undoes_add_quaternion_weighted() {
+        let q1 = Quaternion::new(0.9961947f64, 0.0871557f64, 0f64, 0f64);
+        let q1 = UnitQuaternion::from_quaternion(q1);
+
+        let mut avg = QuaternionAverager::new();
+        avg.add_quaternion_weighted(&q1, 0.5f64);
+        avg.add_quaternion_weighted(&q1, 0.25f64);
+        avg.remove_quaternion_weighted(&q1, 0.25f64);
+
+        assert_relative_eq!(avg.weight_sum, 0.5f64, max_relative = 0.000001);
+    }
+
+    #[test]
+    #[should_panic]
+    fn remove_quaternion_weighted_panics_on_zero_weight() {
+        let q1 = Quaternion::new(0.9961947f64, 0.0871557f64, 0f64, 0f64);
+        let q1 = UnitQuaternion::from_quaternion(q1);
+
+        let mut avg = QuaternionAverager::new();
+        avg.add_quaternion_weighted(&q1, 0.5f64);
+        avg.remove_quaternion_weighted(&q1, 0f64);
+    }
+
+    #[test]
+    #[should_panic]
+    fn calc_average_panics_on_nonpositive_weight_sum() {
+        let q1 = Quaternion::new(0.9961947f64, 0.0871557f64, 0f64, 0f64);
+        let q1 = UnitQuaternion::from_quaternion(q1);
+
+        let mut avg = QuaternionAverager::new();
+        avg.add_quaternion(&q1);
+        avg.remove_quaternion(&q1);
+
+        avg.calc_average();
+    }
+
+    #[test]
+    #[should_panic]
+    fn add_quaternion_weighted_panics_on_zero_weight() {
+        let q1 = Quaternion::new(0.9961947f64, 0.0871557f64, 0f64, 0f64);
+        let q1 = UnitQuaternion::from_quaternion(q1);
+
+        let mut avg = QuaternionAverager::new();
+        avg.add_quaternion_weighted(&q1, 0f64);
+    }
+
+    #[test]
+    #[should_panic]
+    fn weighted_mean_of_panics_on_zero_weight() {
+        let q1 = Quaternion::new(0.9961947f64, 0.0871557f64, 0f64, 0f64);
+        let q1 = UnitQuaternion::from_quaternion(q1);
+        let q2 = Quaternion::new(0.9848078f64, 0.1736482f64, 0f64, 0f64);
+        let q2 = UnitQuaternion::from_quaternion(q2);
+
+        QuaternionAverager::weighted_mean_of([(q1, 0f64), (q2, 1f64)]);
+    }
+
+    #[test]
+    fn slerp_blend_matches_eigen_average_for_two_quaternions() {
+        let q1 = Quaternion::new(0.9961947f64, 0.0871557f64, 0f64, 0f64);
+        let q1 = UnitQuaternion::from_quaternion(q1);
+        let q2 = Quaternion::new(0.9848078f64, 0.1736482f64, 0f64, 0f64);
+        let q2 = UnitQuaternion::from_quaternion(q2);
+
+        let mut avg = QuaternionAverager::new();
+        avg.add_quaternion(&q1);
+        avg.add_quaternion(&q2);
+        let qr = avg.calc_average();
+
+        let q = slerp_blend(&q1, &q2, 0.5f64);
+
+        assert_relative_eq!(q, qr, max_relative = 0.0001);
+    }
+
+    #[test]
+    fn slerp_blend_endpoints() {
+        let q1 = Quaternion::new(0.9961947f64, 0.0871557f64, 0f64, 0f64);
+        let q1 = UnitQuaternion::from_quaternion(q1);
+        let q2 = Quaternion::new(0.9848078f64, 0.1736482f64, 0f64, 0f64);
+        let q2 = UnitQuaternion::from_quaternion(q2);
+
+        assert_relative_eq!(slerp_blend(&q1, &q2, 0f64), q1, max_relative = 0.00001);
+        assert_relative_eq!(slerp_blend(&q1, &q2, 1f64), q2, max_relative = 0.00001);
+    }
+
+    #[test]
+    fn nlerp_blend_endpoints() {
+        let q1 = Quaternion::new(0.9961947f64, 0.0871557f64, 0f64, 0f64);
+        let q1 = UnitQuaternion::from_quaternion(q1);
+        let q2 = Quaternion::new(0.9848078f64, 0.1736482f64, 0f64, 0f64);
+        let q2 = UnitQuaternion::from_quaternion(q2);
+
+        assert_relative_eq!(nlerp_blend(&q1, &q2, 0f64), q1, max_relative = 0.00001);
+        assert_relative_eq!(nlerp_blend(&q1, &q2, 1f64), q2, max_relative = 0.00001);
+    }
+
+    #[test]
+    fn slerp_blend_takes_shorter_arc_for_antipodal_quaternions() {
+        let q1 = Quaternion::new(0.9961947f64, 0.0871557f64, 0f64, 0f64);
+        let q1 = UnitQuaternion::from_quaternion(q1);
+        let q2 = Quaternion::new(-0.9961947f64, -0.0871557f64, 0f64, 0f64);
+        let q2 = UnitQuaternion::from_quaternion(q2);
+
+        let q = slerp_blend(&q1, &q2, 0.5f64);
+
+        assert_relative_eq!(q, q1, max_relative = 0.00001);
+    }
+
+    #[test]
+    fn raw_parts_round_trip() {
+        let q1 = Quaternion::new(0.9961947f64, 0.0871557f64, 0f64, 0f64);
+        let q1 = UnitQuaternion::from_quaternion(q1);
+        let q2 = Quaternion::new(0.9848078f64, 0.1736482f64, 0f64, 0f64);
+        let q2 = UnitQuaternion::from_quaternion(q2);
+
+        let mut avg = QuaternionAverager::new();
+        avg.add_quaternion(&q1);
+        avg.add_quaternion(&q2);
+        let qr = avg.calc_average();
+
+        let (matrix, weight_sum) = avg.into_raw_parts();
+        let resumed = QuaternionAverager::from_raw_parts(matrix, weight_sum);
+
+        assert_relative_eq!(resumed.calc_average(), qr, max_relative = 0.000001);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_round_trip() {
+        let q1 = Quaternion::new(0.9961947f64, 0.0871557f64, 0f64, 0f64);
+        let q1 = UnitQuaternion::from_quaternion(q1);
+        let q2 = Quaternion::new(0.9848078f64, 0.1736482f64, 0f64, 0f64);
+        let q2 = UnitQuaternion::from_quaternion(q2);
+
+        let mut avg = QuaternionAverager::new();
+        avg.add_quaternion(&q1);
+        avg.add_quaternion(&q2);
+        let qr = avg.calc_average();
+
+        let json = serde_json::to_string(&avg).unwrap();
+        let avg: QuaternionAverager<f64> = serde_json::from_str(&json).unwrap();
+
+        assert_relative_eq!(avg.calc_average(), qr, max_relative = 0.000001);
+    }
+
+    #[test]
+    fn merge_matches_serial_average() {
+        let q1 = Quaternion::new(0.9961947f64, 0.0871557f64, 0f64, 0f64);
+        let q1 = UnitQuaternion::from_quaternion(q1);
+        let q2 = Quaternion::new(0.9848078f64, 0.1736482f64, 0f64, 0f64);
+        let q2 = UnitQuaternion::from_quaternion(q2);
+
+        let mut serial = QuaternionAverager::new();
+        serial.add_quaternion(&q1);
+        serial.add_quaternion(&q2);
+
+        let mut chunk_a = QuaternionAverager::new();
+        chunk_a.add_quaternion(&q1);
+        let mut chunk_b = QuaternionAverager::new();
+        chunk_b.add_quaternion(&q2);
+        chunk_a.merge(&chunk_b);
+
+        assert_relative_eq!(chunk_a.calc_average(), serial.calc_average(), max_relative = 0.000001);
+
+        let mut chunk_a = QuaternionAverager::new();
+        chunk_a.add_quaternion(&q1);
+        chunk_a += &chunk_b;
+        assert_relative_eq!(chunk_a.calc_average(), serial.calc_average(), max_relative = 0.000001);
+
+        let mut chunk_a = QuaternionAverager::new();
+        chunk_a.add_quaternion(&q1);
+        let merged = chunk_a + chunk_b;
+        assert_relative_eq!(merged.calc_average(), serial.calc_average(), max_relative = 0.000001);
+    }
 }
\ No newline at end of file